@@ -1,8 +1,10 @@
+extern crate ntapi;
 extern crate winapi;
 
 pub mod snapshot;
 pub mod module;
 pub mod process;
+pub mod thread;
 pub mod utils;
 
 #[cfg(test)]
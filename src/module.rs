@@ -1,11 +1,52 @@
-use winapi::shared::minwindef::DWORD;
+use std::mem::size_of;
+
+use winapi::shared::minwindef::{DWORD, WORD};
+use winapi::um::winnt::{
+    IMAGE_DOS_HEADER, IMAGE_DOS_SIGNATURE, IMAGE_EXPORT_DIRECTORY, IMAGE_NT_HEADERS32,
+    IMAGE_NT_HEADERS64, IMAGE_NT_OPTIONAL_HDR32_MAGIC, IMAGE_NT_OPTIONAL_HDR64_MAGIC,
+    IMAGE_NT_SIGNATURE,
+};
+
+use process::Process;
+use utils::{WinErrorKind, WinResult};
 
 pub struct Module {
     pub(crate) name: String,
     /// Memory address of the [`Module`] relative to the process
-    pub(crate) address: DWORD,
+    pub(crate) address: usize,
     /// Length of the [`Module`] in bytes
-    pub(crate) len: DWORD,
+    pub(crate) len: usize,
+}
+
+/// Result of resolving an export by name
+#[derive(Debug)]
+pub enum Export {
+    /// Address of the exported function in the target process
+    Address(usize),
+    /// The export is forwarded to `"Module.FunctionName"` in another module, rather
+    /// than implemented by this one
+    Forwarded(String),
+}
+
+/// Whether `magic` identifies a PE32 or PE32+ (64-bit) optional header
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum PeBitness {
+    Pe32,
+    Pe32Plus,
+}
+
+fn pe_bitness(magic: WORD) -> WinResult<PeBitness> {
+    match magic {
+        IMAGE_NT_OPTIONAL_HDR64_MAGIC => Ok(PeBitness::Pe32Plus),
+        IMAGE_NT_OPTIONAL_HDR32_MAGIC => Ok(PeBitness::Pe32),
+        _ => Err(WinErrorKind::ResolveExportError),
+    }
+}
+
+/// `true` if `function_rva` falls inside the export directory itself, which means the export
+/// is forwarded to another module's `"Module.FunctionName"` rather than implemented here
+fn is_forwarded_export(function_rva: DWORD, export_dir_rva: DWORD, export_dir_size: DWORD) -> bool {
+    function_rva >= export_dir_rva && function_rva < export_dir_rva + export_dir_size
 }
 
 impl Module {
@@ -14,11 +55,106 @@ impl Module {
         &self.name
     }
     #[inline]
-    pub fn address(&self) -> DWORD {
+    pub fn address(&self) -> usize {
         self.address
     }
     #[inline]
-    pub fn len(&self) -> DWORD {
+    pub fn len(&self) -> usize {
         self.len
     }
+
+    /// Resolve an exported function's address by name, by parsing the PE export table
+    /// out of the module's image in `process`'s memory, the same data `GetProcAddress`
+    /// reads locally.
+    /// [Reference]: https://docs.microsoft.com/en-us/windows/win32/debug/pe-format#export-directory-table
+    pub fn resolve_export(&self, process: &Process, name: &str) -> WinResult<Export> {
+        let dos_header = process.read_mem::<IMAGE_DOS_HEADER>(self.address)?;
+        if dos_header.e_magic != IMAGE_DOS_SIGNATURE {
+            return Err(WinErrorKind::ResolveExportError);
+        }
+
+        let nt_headers_addr = self.address + dos_header.e_lfanew as usize;
+        // Read as PE32 first: `Signature` and `OptionalHeader.Magic` sit at the same
+        // offsets regardless of whether the image is actually PE32 or PE32+
+        let nt_headers32 = process.read_mem::<IMAGE_NT_HEADERS32>(nt_headers_addr)?;
+        if nt_headers32.Signature != IMAGE_NT_SIGNATURE {
+            return Err(WinErrorKind::ResolveExportError);
+        }
+
+        let export_data_dir = match pe_bitness(nt_headers32.OptionalHeader.Magic)? {
+            PeBitness::Pe32Plus => {
+                process.read_mem::<IMAGE_NT_HEADERS64>(nt_headers_addr)?.OptionalHeader.DataDirectory[0]
+            }
+            PeBitness::Pe32 => nt_headers32.OptionalHeader.DataDirectory[0],
+        };
+
+        if export_data_dir.VirtualAddress == 0 {
+            return Err(WinErrorKind::ResolveExportError);
+        }
+
+        let export_dir_addr = self.address + export_data_dir.VirtualAddress as usize;
+        let export_dir = process.read_mem::<IMAGE_EXPORT_DIRECTORY>(export_dir_addr)?;
+
+        let names_addr = self.address + export_dir.AddressOfNames as usize;
+        let ordinals_addr = self.address + export_dir.AddressOfNameOrdinals as usize;
+        let functions_addr = self.address + export_dir.AddressOfFunctions as usize;
+
+        for i in 0..export_dir.NumberOfNames as usize {
+            // A single unreadable name RVA shouldn't abort the whole lookup — skip it and
+            // keep scanning the rest of the (possibly still matching) export table
+            let name_rva = match process.read_mem::<DWORD>(names_addr + i * size_of::<DWORD>()) {
+                Ok(rva) => rva,
+                Err(_) => continue,
+            };
+            let candidate = match process.read_c_string(self.address + name_rva as usize) {
+                Ok(candidate) => candidate,
+                Err(_) => continue,
+            };
+            if candidate != name {
+                continue;
+            }
+
+            let ordinal = process.read_mem::<WORD>(ordinals_addr + i * size_of::<WORD>())?;
+            let function_rva = process.read_mem::<DWORD>(functions_addr + ordinal as usize * size_of::<DWORD>())?;
+
+            return if is_forwarded_export(function_rva, export_data_dir.VirtualAddress, export_data_dir.Size) {
+                Ok(Export::Forwarded(process.read_c_string(self.address + function_rva as usize)?))
+            } else {
+                Ok(Export::Address(self.address + function_rva as usize))
+            };
+        }
+
+        Err(WinErrorKind::ResolveExportError)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn pe_bitness_selects_pe32_vs_pe32_plus() {
+        assert_eq!(pe_bitness(IMAGE_NT_OPTIONAL_HDR32_MAGIC).unwrap(), PeBitness::Pe32);
+        assert_eq!(pe_bitness(IMAGE_NT_OPTIONAL_HDR64_MAGIC).unwrap(), PeBitness::Pe32Plus);
+    }
+
+    #[test]
+    fn pe_bitness_rejects_unknown_magic() {
+        assert!(pe_bitness(0x1234).is_err());
+    }
+
+    #[test]
+    fn is_forwarded_export_detects_rva_inside_export_directory() {
+        let export_dir_rva = 0x2000;
+        let export_dir_size = 0x100;
+
+        // Inside the directory (including its exact start) => forwarded
+        assert!(is_forwarded_export(0x2000, export_dir_rva, export_dir_size));
+        assert!(is_forwarded_export(0x2050, export_dir_rva, export_dir_size));
+        // The end boundary is exclusive => not forwarded
+        assert!(!is_forwarded_export(0x2100, export_dir_rva, export_dir_size));
+
+        // Outside the directory entirely => a real function address
+        assert!(!is_forwarded_export(0x1000, export_dir_rva, export_dir_size));
+    }
 }
\ No newline at end of file
@@ -1,16 +1,80 @@
-use std::mem::{size_of, zeroed};
+use std::mem::{size_of, zeroed, MaybeUninit};
 use std::ptr::null_mut;
 
-use winapi::shared::minwindef::{DWORD, FALSE, LPCVOID, LPVOID, TRUE};
+use winapi::shared::minwindef::{BOOL, DWORD, FALSE, LPCVOID, LPVOID, TRUE};
+use winapi::shared::ntdef::NTSTATUS;
+use winapi::um::errhandlingapi::GetLastError;
 use winapi::um::handleapi::INVALID_HANDLE_VALUE;
 use winapi::um::memoryapi::{ReadProcessMemory, WriteProcessMemory};
 use winapi::um::processthreadsapi::OpenProcess;
-use winapi::um::tlhelp32::{Module32FirstW, Module32NextW, MODULEENTRY32W, Process32FirstW, Process32NextW, PROCESSENTRY32W};
+use winapi::um::tlhelp32::{
+    Module32FirstW, Module32NextW, MODULEENTRY32W, Process32FirstW, Process32NextW, PROCESSENTRY32W,
+    Thread32First, Thread32Next, THREADENTRY32,
+};
 use winapi::um::winnt::{HANDLE, PROCESS_ALL_ACCESS};
+use winapi::um::wow64apiset::IsWow64Process;
+
+use ntapi::ntpsapi::{NtQueryInformationProcess, PROCESS_BASIC_INFORMATION};
 
 use module::Module;
 use snapshot::Snapshot;
-use utils::{close_h, WinErrorKind, WinResult, remove_nil_bytes};
+use thread::Thread;
+use utils::{close_h, decode_utf16, WinErrorKind, WinResult, remove_nil_bytes};
+
+/// `NtQueryInformationProcess` info class values used by this module; only the handful we
+/// actually need are named here rather than pulling in the whole `PROCESSINFOCLASS` enum
+const PROCESS_BASIC_INFORMATION_CLASS: u32 = 0;
+const PROCESS_WOW64_INFORMATION_CLASS: u32 = 26;
+
+#[inline]
+fn nt_success(status: NTSTATUS) -> bool {
+    status >= 0
+}
+
+/// Offset of `PEB.ProcessParameters`, which is 0x20 on x64 and 0x10 on x86
+#[inline]
+fn process_parameters_offset(pointer_size: usize) -> usize {
+    if pointer_size == size_of::<u64>() { 0x20 } else { 0x10 }
+}
+
+/// Offset of `RTL_USER_PROCESS_PARAMETERS.CommandLine`
+#[inline]
+fn command_line_offset(pointer_size: usize) -> usize {
+    if pointer_size == size_of::<u64>() { 0x70 } else { 0x40 }
+}
+
+/// Offset of `RTL_USER_PROCESS_PARAMETERS.CurrentDirectory.DosPath`
+#[inline]
+fn current_directory_offset(pointer_size: usize) -> usize {
+    if pointer_size == size_of::<u64>() { 0x38 } else { 0x24 }
+}
+
+/// Offset of `RTL_USER_PROCESS_PARAMETERS.Environment`
+#[inline]
+fn environment_offset(pointer_size: usize) -> usize {
+    if pointer_size == size_of::<u64>() { 0x80 } else { 0x48 }
+}
+
+/// Split a flat double-nil-terminated block of nil-terminated UTF-16 strings (the layout of a
+/// process's environment block) into `"NAME=VALUE"` strings. Returns whether the block's real
+/// terminator (two adjacent nil `WCHAR`s) was found, so a caller streaming the block in from
+/// remote memory knows whether it needs to keep reading.
+fn split_environment_block(utf16: &[u16]) -> (Vec<String>, bool) {
+    let mut variables = Vec::new();
+    let mut start = 0;
+    for i in 0..utf16.len() {
+        if utf16[i] == 0 {
+            if i == start {
+                return (variables, true);
+            }
+            if let Ok(var) = String::from_utf16(&utf16[start..i]) {
+                variables.push(var);
+            }
+            start = i + 1;
+        }
+    }
+    (variables, false)
+}
 
 /// Represents a system process, posses a PID, name and an open [`HANDLE`]
 pub struct Process {
@@ -19,13 +83,64 @@ pub struct Process {
     handle: HANDLE,
 }
 
+/// Name + PID of a process, as returned by [`Process::all`] without opening a [`HANDLE`]
+pub struct ProcessInfo {
+    name: String,
+    pid: DWORD,
+}
+
+impl ProcessInfo {
+    #[inline]
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+    #[inline]
+    pub fn pid(&self) -> DWORD {
+        self.pid
+    }
+}
+
+/// `true` if `candidate` should be considered a match for `query`, either requiring an
+/// exact match or falling back to the crate's historical prefix match
+#[inline]
+fn name_matches(candidate: &str, query: &str, exact: bool) -> bool {
+    if exact {
+        candidate == query
+    } else {
+        candidate.starts_with(query)
+    }
+}
+
 impl Process {
-    /// Find a [`Process`] from it's executable's name
+    /// Find a [`Process`] from it's executable's name, matching a prefix of `name` and
+    /// requesting [`PROCESS_ALL_ACCESS`]
+    #[inline]
+    pub fn find(name: &str) -> WinResult<Self> {
+        Self::find_by(name, false, PROCESS_ALL_ACCESS)
+    }
+
+    /// Find a [`Process`] from it's executable's name, requiring an exact match so e.g.
+    /// `find_exact("note.exe")` won't attach to `notepad++.exe`, requesting
+    /// [`PROCESS_ALL_ACCESS`]
+    #[inline]
+    pub fn find_exact(name: &str) -> WinResult<Self> {
+        Self::find_by(name, true, PROCESS_ALL_ACCESS)
+    }
+
+    /// Find a [`Process`] from it's executable's name, matching a prefix of `name`, requesting
+    /// only `access` rather than [`PROCESS_ALL_ACCESS`] — useful against protected or
+    /// higher-integrity processes that deny all-access but will still grant e.g.
+    /// `PROCESS_QUERY_INFORMATION | PROCESS_VM_READ`
+    #[inline]
+    pub fn find_with_access(name: &str, access: DWORD) -> WinResult<Self> {
+        Self::find_by(name, false, access)
+    }
+
     /// [Reference(s)]:
     /// https://docs.microsoft.com/en-us/windows/win32/api/tlhelp32/nf-tlhelp32-process32firstw
     /// https://docs.microsoft.com/en-us/windows/win32/api/tlhelp32/nf-tlhelp32-process32nextw
     /// https://docs.microsoft.com/en-us/windows/win32/api/tlhelp32/ns-tlhelp32-processentry32w
-    pub fn find(name: &str) -> WinResult<Self> {
+    fn find_by(name: &str, exact: bool, access: DWORD) -> WinResult<Self> {
         unsafe {
             let snapshot = Snapshot::process();
 
@@ -38,15 +153,18 @@ impl Process {
                 Process32FirstW(snapshot.handle(), &mut p_entry) != FALSE {
                 while {
                     if let Ok(p_name) = remove_nil_bytes(&p_entry.szExeFile) {
-                        if p_name.starts_with(name) {
+                        if name_matches(&p_name, name, exact) {
                             let pid = p_entry.th32ProcessID;
-                            // Desire all access despite *probably* only needing VM_READ and VM_WRITE
-                            let h_proc = OpenProcess(PROCESS_ALL_ACCESS, FALSE, pid);
-                            return Ok(Process {
-                                name: p_name,
-                                pid,
-                                handle: h_proc,
-                            });
+                            let h_proc = OpenProcess(access, FALSE, pid);
+                            return if !h_proc.is_null() {
+                                Ok(Process {
+                                    name: p_name,
+                                    pid,
+                                    handle: h_proc,
+                                })
+                            } else {
+                                Err(WinErrorKind::OpenProcessError(GetLastError()))
+                            };
                         }
                     }
 
@@ -58,12 +176,52 @@ impl Process {
         Err(WinErrorKind::FindProcessError)
     }
 
-    /// Find a process's module (dll) by it's name
+    /// List every running process's name and PID, without opening a [`HANDLE`] to each
+    /// [Reference(s)]:
+    /// https://docs.microsoft.com/en-us/windows/win32/api/tlhelp32/nf-tlhelp32-process32firstw
+    /// https://docs.microsoft.com/en-us/windows/win32/api/tlhelp32/nf-tlhelp32-process32nextw
+    pub fn all() -> WinResult<Vec<ProcessInfo>> {
+        unsafe {
+            let snapshot = Snapshot::process();
+
+            let mut p_entry = zeroed::<PROCESSENTRY32W>();
+            p_entry.dwSize = size_of::<PROCESSENTRY32W>() as DWORD;
+
+            let mut processes = Vec::new();
+
+            if !snapshot.handle().is_null() &&
+                snapshot.handle() != INVALID_HANDLE_VALUE &&
+                Process32FirstW(snapshot.handle(), &mut p_entry) != FALSE {
+                while {
+                    if let Ok(name) = remove_nil_bytes(&p_entry.szExeFile) {
+                        processes.push(ProcessInfo { name, pid: p_entry.th32ProcessID });
+                    }
+
+                    Process32NextW(snapshot.handle(), &mut p_entry) != FALSE
+                } {}
+            }
+
+            Ok(processes)
+        }
+    }
+
+    /// Find a process's module (dll) by it's name, matching a prefix of `name`
+    #[inline]
+    pub fn find_module(&self, name: &str) -> WinResult<Module> {
+        self.find_module_by(name, false)
+    }
+
+    /// Find a process's module (dll) by it's name, requiring an exact match
+    #[inline]
+    pub fn find_module_exact(&self, name: &str) -> WinResult<Module> {
+        self.find_module_by(name, true)
+    }
+
     /// [Reference(s)]:
     /// https://docs.microsoft.com/en-us/windows/win32/api/tlhelp32/nf-tlhelp32-module32firstw
     /// https://docs.microsoft.com/en-us/windows/win32/api/tlhelp32/nf-tlhelp32-module32nextw
     /// https://docs.microsoft.com/en-us/windows/win32/api/tlhelp32/ns-tlhelp32-moduleentry32w
-    pub fn find_module(&self, name: &str) -> WinResult<Module> {
+    fn find_module_by(&self, name: &str, exact: bool) -> WinResult<Module> {
         unsafe {
             let snapshot = Snapshot::module(self);
 
@@ -76,11 +234,11 @@ impl Process {
                 Module32FirstW(snapshot.handle(), &mut m_entry) != FALSE {
                 while {
                     if let Ok(m_name) = remove_nil_bytes(&m_entry.szModule) {
-                        if m_name.starts_with(name) {
+                        if name_matches(&m_name, name, exact) {
                             return Ok(Module {
                                 name: m_name,
-                                address: m_entry.modBaseAddr as DWORD,
-                                len: m_entry.modBaseSize,
+                                address: m_entry.modBaseAddr as usize,
+                                len: m_entry.modBaseSize as usize,
                             });
                         }
                     }
@@ -93,14 +251,83 @@ impl Process {
         Err(WinErrorKind::FindModuleError)
     }
 
-    /// Write to a process's memory, not relative to module offset
+    /// List every module (dll) loaded into this process
+    /// [Reference(s)]:
+    /// https://docs.microsoft.com/en-us/windows/win32/api/tlhelp32/nf-tlhelp32-module32firstw
+    /// https://docs.microsoft.com/en-us/windows/win32/api/tlhelp32/nf-tlhelp32-module32nextw
+    pub fn modules(&self) -> WinResult<Vec<Module>> {
+        unsafe {
+            let snapshot = Snapshot::module(self);
+
+            let mut m_entry = zeroed::<MODULEENTRY32W>();
+            m_entry.dwSize = size_of::<MODULEENTRY32W>() as DWORD;
+
+            let mut modules = Vec::new();
+
+            if !snapshot.handle().is_null() &&
+                snapshot.handle() != INVALID_HANDLE_VALUE &&
+                Module32FirstW(snapshot.handle(), &mut m_entry) != FALSE {
+                while {
+                    if let Ok(name) = remove_nil_bytes(&m_entry.szModule) {
+                        modules.push(Module {
+                            name,
+                            address: m_entry.modBaseAddr as usize,
+                            len: m_entry.modBaseSize as usize,
+                        });
+                    }
+
+                    Module32NextW(snapshot.handle(), &mut m_entry) != FALSE
+                } {}
+            }
+
+            Ok(modules)
+        }
+    }
+
+    /// List the threads belonging to this process
+    /// [Reference(s)]:
+    /// https://docs.microsoft.com/en-us/windows/win32/api/tlhelp32/nf-tlhelp32-thread32first
+    /// https://docs.microsoft.com/en-us/windows/win32/api/tlhelp32/nf-tlhelp32-thread32next
+    /// https://docs.microsoft.com/en-us/windows/win32/api/tlhelp32/ns-tlhelp32-threadentry32
+    pub fn threads(&self) -> WinResult<Vec<Thread>> {
+        unsafe {
+            let snapshot = Snapshot::thread(self);
+
+            let mut t_entry = zeroed::<THREADENTRY32>();
+            // `dwSize` must be initialized with size of THREADENTRY32 before Thread32First or Thread32Next are called
+            t_entry.dwSize = size_of::<THREADENTRY32>() as DWORD;
+
+            let mut threads = Vec::new();
+
+            if !snapshot.handle().is_null() &&
+                snapshot.handle() != INVALID_HANDLE_VALUE &&
+                Thread32First(snapshot.handle(), &mut t_entry) != FALSE {
+                while {
+                    // The toolhelp snapshot covers every thread on the system, so filter down to this process
+                    if t_entry.th32OwnerProcessID == self.pid {
+                        threads.push(Thread {
+                            tid: t_entry.th32ThreadID,
+                            owner_pid: t_entry.th32OwnerProcessID,
+                            base_priority: t_entry.tpBasePri,
+                        });
+                    }
+
+                    Thread32Next(snapshot.handle(), &mut t_entry) != FALSE
+                } {}
+            }
+
+            Ok(threads)
+        }
+    }
+
+    /// Write a buffer of arbitrary length to a process's memory, not relative to module offset
     /// [Reference]: https://docs.microsoft.com/en-us/windows/win32/api/memoryapi/nf-memoryapi-writeprocessmemory
-    pub fn write_mem<T>(&self, buffer: &T, address: DWORD) -> WinResult<()> {
+    pub fn write_bytes(&self, address: usize, data: &[u8]) -> WinResult<()> {
         unsafe {
             if WriteProcessMemory(self.handle,
                                   address as LPVOID,
-                                  buffer as *const T as LPCVOID,
-                                  size_of::<T>(),
+                                  data.as_ptr() as LPCVOID,
+                                  data.len(),
                                   null_mut()) == TRUE {
                 Ok(())
             } else {
@@ -109,26 +336,25 @@ impl Process {
         }
     }
 
-    /// Write to a process's memory relative to the offset of a module
+    /// Write a buffer of arbitrary length to a process's memory relative to the offset of a module
     #[inline]
-    pub fn write_mem_relative<T>(&self, buffer: &T, module_name: &str, address: DWORD) -> WinResult<()> {
+    pub fn write_bytes_relative(&self, module_name: &str, address: usize, data: &[u8]) -> WinResult<()> {
         if let Ok(module) = self.find_module(module_name) {
-            self.write_mem(buffer, module.address() + address)
+            self.write_bytes(module.address() + address, data)
         } else {
             Err(WinErrorKind::WriteMemoryError)
         }
     }
 
-    /// Read a process's memory, not relative to module offset
+    /// Read a buffer of `len` bytes from a process's memory, not relative to module offset
     /// [Reference]: https://docs.microsoft.com/en-us/windows/win32/api/memoryapi/nf-memoryapi-readprocessmemory
-    pub fn read_mem<T>(&self, address: DWORD) -> WinResult<T> {
+    pub fn read_bytes(&self, address: usize, len: usize) -> WinResult<Vec<u8>> {
         unsafe {
-            // Initialize buffer
-            let mut buf = zeroed::<T>();
+            let mut buf = vec![0u8; len];
             if ReadProcessMemory(self.handle,
                                  address as LPVOID,
-                                 &mut buf as *mut T as LPVOID,
-                                 size_of::<T>(),
+                                 buf.as_mut_ptr() as LPVOID,
+                                 len,
                                  null_mut()) == TRUE {
                 Ok(buf)
             } else {
@@ -137,9 +363,50 @@ impl Process {
         }
     }
 
+    /// Read a buffer of `len` bytes from a process's memory relative to the offset of a module
+    #[inline]
+    pub fn read_bytes_relative(&self, module_name: &str, address: usize, len: usize) -> WinResult<Vec<u8>> {
+        if let Ok(module) = self.find_module(module_name) {
+            self.read_bytes(module.address() + address, len)
+        } else {
+            Err(WinErrorKind::ReadMemoryError)
+        }
+    }
+
+    /// Write to a process's memory, not relative to module offset
+    #[inline]
+    pub fn write_mem<T>(&self, buffer: &T, address: usize) -> WinResult<()> {
+        let bytes = unsafe { std::slice::from_raw_parts(buffer as *const T as *const u8, size_of::<T>()) };
+        self.write_bytes(address, bytes)
+    }
+
+    /// Write to a process's memory relative to the offset of a module
+    #[inline]
+    pub fn write_mem_relative<T>(&self, buffer: &T, module_name: &str, address: usize) -> WinResult<()> {
+        if let Ok(module) = self.find_module(module_name) {
+            self.write_mem(buffer, module.address() + address)
+        } else {
+            Err(WinErrorKind::WriteMemoryError)
+        }
+    }
+
+    /// Read a process's memory, not relative to module offset
+    #[inline]
+    pub fn read_mem<T>(&self, address: usize) -> WinResult<T> {
+        let bytes = self.read_bytes(address, size_of::<T>())?;
+        unsafe {
+            // `zeroed::<T>()` is itself UB for a `T` with no valid all-zero bit pattern
+            // (references, `NonZero*`, niche-optimized enums), even though we immediately
+            // overwrite it — use an uninitialized buffer instead
+            let mut buf = MaybeUninit::<T>::uninit();
+            std::ptr::copy_nonoverlapping(bytes.as_ptr(), buf.as_mut_ptr() as *mut u8, size_of::<T>());
+            Ok(buf.assume_init())
+        }
+    }
+
     /// Read a process's memory address relative to the offset of a module
     #[inline]
-    pub fn read_mem_relative<T>(&self, module_name: &str, address: DWORD) -> WinResult<T> {
+    pub fn read_mem_relative<T>(&self, module_name: &str, address: usize) -> WinResult<T> {
         if let Ok(module) = self.find_module(module_name) {
             self.read_mem(module.address() + address)
         } else {
@@ -147,6 +414,159 @@ impl Process {
         }
     }
 
+    /// Read a nil-terminated ASCII string from the process's memory, as used by PE export
+    /// names and forwarder strings. Reads in small chunks until a nil byte is found or
+    /// `MAX_C_STRING_LEN` is reached, to avoid an unbounded read off a corrupt pointer.
+    pub(crate) fn read_c_string(&self, address: usize) -> WinResult<String> {
+        const CHUNK_LEN: usize = 32;
+        const MAX_C_STRING_LEN: usize = 1024;
+
+        let mut bytes = Vec::new();
+        while bytes.len() < MAX_C_STRING_LEN {
+            let chunk = self.read_bytes(address + bytes.len(), CHUNK_LEN)?;
+            match chunk.iter().position(|&b| b == 0) {
+                Some(nil_pos) => {
+                    bytes.extend_from_slice(&chunk[..nil_pos]);
+                    return String::from_utf8(bytes).map_err(|_| WinErrorKind::ReadMemoryError);
+                }
+                None => bytes.extend_from_slice(&chunk),
+            }
+        }
+
+        Err(WinErrorKind::ReadMemoryError)
+    }
+
+    /// `true` if the target is a 32-bit process running under WOW64 on 64-bit Windows
+    fn is_wow64(&self) -> WinResult<bool> {
+        unsafe {
+            let mut result: BOOL = FALSE;
+            if IsWow64Process(self.handle, &mut result) != FALSE {
+                Ok(result != FALSE)
+            } else {
+                Err(WinErrorKind::QueryProcessInfoError)
+            }
+        }
+    }
+
+    /// Locate the target's PEB, returning its address together with the pointer width
+    /// (4 or 8 bytes) its `RTL_USER_PROCESS_PARAMETERS` was laid out with
+    fn peb(&self) -> WinResult<(usize, usize)> {
+        unsafe {
+            if self.is_wow64()? {
+                // For a WOW64 target, ProcessWow64Information hands back the address of the
+                // 32-bit PEB living inside the target; ProcessBasicInformation's PebBaseAddress
+                // would instead give the native (64-bit) PEB, which has no real parameters.
+                // The returned value is a ULONG_PTR sized to *our* (the querying process's)
+                // pointer width, not the target's — NtQueryInformationProcess rejects a
+                // mismatched buffer length with STATUS_INFO_LENGTH_MISMATCH
+                let mut peb32_addr: usize = 0;
+                let mut returned_len: DWORD = 0;
+                let status = NtQueryInformationProcess(
+                    self.handle,
+                    PROCESS_WOW64_INFORMATION_CLASS,
+                    &mut peb32_addr as *mut usize as LPVOID,
+                    size_of::<usize>() as DWORD,
+                    &mut returned_len,
+                );
+
+                if !nt_success(status) || peb32_addr == 0 {
+                    return Err(WinErrorKind::QueryProcessInfoError);
+                }
+
+                Ok((peb32_addr, size_of::<DWORD>()))
+            } else {
+                let mut info = zeroed::<PROCESS_BASIC_INFORMATION>();
+                let mut returned_len: DWORD = 0;
+                let status = NtQueryInformationProcess(
+                    self.handle,
+                    PROCESS_BASIC_INFORMATION_CLASS,
+                    &mut info as *mut PROCESS_BASIC_INFORMATION as LPVOID,
+                    size_of::<PROCESS_BASIC_INFORMATION>() as DWORD,
+                    &mut returned_len,
+                );
+
+                if !nt_success(status) || info.PebBaseAddress.is_null() {
+                    return Err(WinErrorKind::QueryProcessInfoError);
+                }
+
+                Ok((info.PebBaseAddress as usize, size_of::<usize>()))
+            }
+        }
+    }
+
+    /// Read a pointer-width value out of the target's memory
+    fn read_ptr(&self, address: usize, pointer_size: usize) -> WinResult<usize> {
+        let bytes = self.read_bytes(address, pointer_size)?;
+        if pointer_size == size_of::<u64>() {
+            let mut buf = [0u8; 8];
+            buf.copy_from_slice(&bytes);
+            Ok(u64::from_le_bytes(buf) as usize)
+        } else {
+            let mut buf = [0u8; 4];
+            buf.copy_from_slice(&bytes);
+            Ok(u32::from_le_bytes(buf) as usize)
+        }
+    }
+
+    /// Address of the target's `RTL_USER_PROCESS_PARAMETERS`, together with the pointer width
+    /// to read it with
+    fn process_parameters(&self) -> WinResult<(usize, usize)> {
+        let (peb_addr, pointer_size) = self.peb()?;
+        let params_addr = self.read_ptr(peb_addr + process_parameters_offset(pointer_size), pointer_size)?;
+        Ok((params_addr, pointer_size))
+    }
+
+    /// Read a `UNICODE_STRING` at `offset` within the target's `RTL_USER_PROCESS_PARAMETERS`
+    fn read_unicode_string(&self, params_addr: usize, offset: usize, pointer_size: usize) -> WinResult<String> {
+        let len_bytes = self.read_bytes(params_addr + offset, 2)?;
+        let len = u16::from_le_bytes([len_bytes[0], len_bytes[1]]) as usize;
+        if len == 0 {
+            return Ok(String::new());
+        }
+
+        // `Buffer` follows `Length`/`MaximumLength` (2 bytes each), aligned to pointer width
+        let buffer_addr = self.read_ptr(params_addr + offset + pointer_size, pointer_size)?;
+        let raw = self.read_bytes(buffer_addr, len)?;
+        let utf16: Vec<u16> = raw.chunks_exact(2).map(|c| u16::from_le_bytes([c[0], c[1]])).collect();
+        decode_utf16(&utf16).map_err(|_| WinErrorKind::ReadMemoryError)
+    }
+
+    /// Read the target's launch command line out of its PEB-resident process parameters
+    pub fn command_line(&self) -> WinResult<String> {
+        let (params_addr, pointer_size) = self.process_parameters()?;
+        self.read_unicode_string(params_addr, command_line_offset(pointer_size), pointer_size)
+    }
+
+    /// Read the target's current working directory out of its PEB-resident process parameters
+    pub fn working_directory(&self) -> WinResult<String> {
+        let (params_addr, pointer_size) = self.process_parameters()?;
+        self.read_unicode_string(params_addr, current_directory_offset(pointer_size), pointer_size)
+    }
+
+    /// Read the target's environment block out of its PEB-resident process parameters, as a
+    /// list of `"NAME=VALUE"` strings
+    pub fn environment(&self) -> WinResult<Vec<String>> {
+        let (params_addr, pointer_size) = self.process_parameters()?;
+        let environment_addr = self.read_ptr(params_addr + environment_offset(pointer_size), pointer_size)?;
+
+        const CHUNK_LEN: usize = 512;
+        const MAX_ENVIRONMENT_LEN: usize = 64 * 1024;
+
+        // The environment is a double-nil-terminated block of nil-terminated UTF-16 strings,
+        // with no reliable size field exposed in the public RTL_USER_PROCESS_PARAMETERS
+        // layout, so grow the read until the terminator is found
+        let mut raw = Vec::new();
+        loop {
+            raw.extend_from_slice(&self.read_bytes(environment_addr + raw.len(), CHUNK_LEN)?);
+
+            let utf16: Vec<u16> = raw.chunks_exact(2).map(|c| u16::from_le_bytes([c[0], c[1]])).collect();
+            let (variables, terminated) = split_environment_block(&utf16);
+            if terminated || raw.len() >= MAX_ENVIRONMENT_LEN {
+                return Ok(variables);
+            }
+        }
+    }
+
     #[inline]
     pub fn name(&self) -> &str {
         &self.name
@@ -169,3 +589,58 @@ impl Drop for Process {
         close_h(self.handle)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn name_matches_requires_full_equality_when_exact() {
+        assert!(name_matches("note.exe", "note.exe", true));
+        assert!(!name_matches("notepad++.exe", "note.exe", true));
+    }
+
+    #[test]
+    fn name_matches_falls_back_to_prefix_when_not_exact() {
+        assert!(name_matches("notepad++.exe", "note", false));
+        assert!(!name_matches("note.exe", "notepad++.exe", false));
+    }
+
+    #[test]
+    fn process_parameters_offset_matches_known_peb_layout() {
+        assert_eq!(process_parameters_offset(8), 0x20);
+        assert_eq!(process_parameters_offset(4), 0x10);
+    }
+
+    #[test]
+    fn unicode_string_offsets_differ_between_x86_and_x64() {
+        assert_eq!(command_line_offset(8), 0x70);
+        assert_eq!(command_line_offset(4), 0x40);
+        assert_eq!(current_directory_offset(8), 0x38);
+        assert_eq!(current_directory_offset(4), 0x24);
+        assert_eq!(environment_offset(8), 0x80);
+        assert_eq!(environment_offset(4), 0x48);
+    }
+
+    #[test]
+    fn split_environment_block_stops_at_double_nil_terminator() {
+        let mut utf16: Vec<u16> = "FOO=bar".encode_utf16().collect();
+        utf16.push(0);
+        utf16.extend("BAZ=qux".encode_utf16());
+        utf16.push(0);
+        utf16.push(0); // terminator
+        utf16.push('X' as u16); // trailing garbage past the terminator must be ignored
+
+        let (variables, terminated) = split_environment_block(&utf16);
+        assert!(terminated);
+        assert_eq!(variables, vec!["FOO=bar".to_string(), "BAZ=qux".to_string()]);
+    }
+
+    #[test]
+    fn split_environment_block_reports_unterminated_when_truncated() {
+        let utf16: Vec<u16> = "FOO=bar".encode_utf16().collect();
+        let (variables, terminated) = split_environment_block(&utf16);
+        assert!(!terminated);
+        assert!(variables.is_empty());
+    }
+}
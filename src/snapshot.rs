@@ -1,4 +1,4 @@
-use winapi::um::tlhelp32::{CreateToolhelp32Snapshot, TH32CS_SNAPMODULE, TH32CS_SNAPMODULE32, TH32CS_SNAPPROCESS};
+use winapi::um::tlhelp32::{CreateToolhelp32Snapshot, TH32CS_SNAPMODULE, TH32CS_SNAPMODULE32, TH32CS_SNAPPROCESS, TH32CS_SNAPTHREAD};
 use winapi::um::winnt::HANDLE;
 
 use process::Process;
@@ -20,6 +20,11 @@ impl Snapshot {
     pub fn module(process: &Process) -> Self {
         unsafe { Snapshot(CreateToolhelp32Snapshot(TH32CS_SNAPMODULE | TH32CS_SNAPMODULE32, process.pid())) }
     }
+    /// Creates a snapshot handle to parse into [`Thread32First`] and [`Thread32Next`]
+    #[inline]
+    pub fn thread(process: &Process) -> Self {
+        unsafe { Snapshot(CreateToolhelp32Snapshot(TH32CS_SNAPTHREAD, process.pid())) }
+    }
     /// Returns the handle created by [`CreateToolhelp32Snapshot`]
     #[inline]
     pub fn handle(&self) -> HANDLE {
@@ -0,0 +1,23 @@
+use winapi::shared::minwindef::DWORD;
+use winapi::shared::ntdef::LONG;
+
+pub struct Thread {
+    pub(crate) tid: DWORD,
+    pub(crate) owner_pid: DWORD,
+    pub(crate) base_priority: LONG,
+}
+
+impl Thread {
+    #[inline]
+    pub fn tid(&self) -> DWORD {
+        self.tid
+    }
+    #[inline]
+    pub fn owner_pid(&self) -> DWORD {
+        self.owner_pid
+    }
+    #[inline]
+    pub fn base_priority(&self) -> LONG {
+        self.base_priority
+    }
+}
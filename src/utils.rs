@@ -1,5 +1,6 @@
 use std::string::FromUtf16Error;
 
+use winapi::shared::minwindef::DWORD;
 use winapi::um::handleapi::{CloseHandle, INVALID_HANDLE_VALUE};
 use winapi::um::winnt::{HANDLE, WCHAR};
 
@@ -11,6 +12,14 @@ pub enum WinErrorKind {
     WriteMemoryError,
     FindProcessError,
     FindModuleError,
+    ResolveExportError,
+    QueryProcessInfoError,
+    /// A matching process was found but [`OpenProcess`] failed to open it with the
+    /// requested access rights; carries the [`GetLastError`] code
+    ///
+    /// [`OpenProcess`]: winapi::um::processthreadsapi::OpenProcess
+    /// [`GetLastError`]: winapi::um::errhandlingapi::GetLastError
+    OpenProcessError(DWORD),
 }
 
 /// For internal use only: safe wrapper for [`CloseHandle`]
@@ -21,13 +30,20 @@ pub fn close_h(handle: HANDLE) {
     }
 }
 
-pub fn remove_nil_bytes<const C_STR_SIZE: usize>(c_style_str: &[WCHAR; C_STR_SIZE]) -> Result<String, FromUtf16Error> {
-    for i in 0..c_style_str.len() {
-        if c_style_str[i] == 0 {
-            return String::from_utf16(&c_style_str[..i]);
+/// Decode a UTF-16 buffer of known length, stopping at the first nil `WCHAR` if one is
+/// present. Used both for the fixed-size `szExeFile`/`szModule` arrays toolhelp fills in and
+/// for length-bounded buffers read out of another process's memory (e.g. a `UNICODE_STRING`)
+pub fn decode_utf16(buf: &[WCHAR]) -> Result<String, FromUtf16Error> {
+    for i in 0..buf.len() {
+        if buf[i] == 0 {
+            return String::from_utf16(&buf[..i]);
         }
     }
-    // If loop falls thought it means all `C_STR_SIZE`
-    // `WCHAR`s of the `c_style_str` were non-nil
-    String::from_utf16(c_style_str)
+    // If loop falls thought it means all `buf.len()`
+    // `WCHAR`s of `buf` were non-nil
+    String::from_utf16(buf)
+}
+
+pub fn remove_nil_bytes<const C_STR_SIZE: usize>(c_style_str: &[WCHAR; C_STR_SIZE]) -> Result<String, FromUtf16Error> {
+    decode_utf16(c_style_str)
 }
\ No newline at end of file